@@ -0,0 +1,246 @@
+use crate::once::Once;
+use core::fmt::{self, Debug};
+use core::mem;
+use core::slice;
+
+/// Keyed collection of static `(K, V)` entries gathered into a contiguous
+/// section of the binary by the linker.
+///
+/// A `DistributedMap` is the keyed counterpart of [`DistributedSlice`]. It is
+/// declared by writing `#[distributed_map]` on a static whose type is
+/// `[(K, V)]`, and entries are registered from anywhere in the dependency graph
+/// by `#[distributed_map(MAP)]`, exactly as with the slice macros. The key `K`
+/// must be a const key such as `&'static str` or an integer.
+///
+/// ```
+/// # struct Request;
+/// # struct Response;
+/// use linkme::distributed_map;
+///
+/// #[distributed_map]
+/// pub static HANDLERS: [(&'static str, fn(&Request) -> Response)] = [..];
+/// ```
+///
+/// The raw entries — [`entries`][DistributedMap::entries] and
+/// [`iter`][DistributedMap::iter] — are always available, including under
+/// `#![no_std]` without an allocator.
+///
+/// # Lookup
+///
+/// Keyed lookup requires the `alloc` feature. Because the gathered section is
+/// only known at runtime, `get`, `contains_key` and `try_build` are served by a
+/// lazily built index: the first lookup collects every entry, sorts their
+/// references by key, checks for duplicate keys, and caches the result so that
+/// subsequent lookups are a binary search. The index is materialized on the
+/// heap and leaked, which is why these methods are gated behind `alloc`; a
+/// `no_std`-without-allocator consumer can still iterate `entries` and match on
+/// keys by hand. Building the index panics if two entries share a key; use
+/// `try_build` to observe the conflict instead.
+///
+/// [`DistributedSlice`]: crate::DistributedSlice
+pub struct DistributedMap<K: 'static, V: 'static> {
+    name: &'static str,
+    section_start: StaticPtr<(K, V)>,
+    section_stop: StaticPtr<(K, V)>,
+    index: &'static OnceIndex<K, V>,
+}
+
+struct StaticPtr<T: ?Sized> {
+    ptr: *const T,
+}
+
+unsafe impl<T: ?Sized> Send for StaticPtr<T> {}
+
+unsafe impl<T: ?Sized> Sync for StaticPtr<T> {}
+
+impl<T: ?Sized> Copy for StaticPtr<T> {}
+
+impl<T: ?Sized> Clone for StaticPtr<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+/// Lazily initialized index over the entries of a [`DistributedMap`], emitted
+/// by the `#[distributed_map]` attribute alongside the map it serves.
+///
+/// The index is a leaked array of entry references sorted by key, cached in the
+/// shared write-once [`Once`] primitive.
+pub type OnceIndex<K, V> = Once<&'static (K, V)>;
+
+/// Error returned by [`DistributedMap::try_build`] when two registered entries
+/// share the same key.
+pub struct DuplicateKey<K: 'static, V: 'static> {
+    name: &'static str,
+    entries: (&'static (K, V), &'static (K, V)),
+}
+
+impl<K, V> DuplicateKey<K, V> {
+    /// The two entries found to share a key.
+    pub fn entries(&self) -> (&'static (K, V), &'static (K, V)) {
+        self.entries
+    }
+}
+
+impl<K, V> Debug for DuplicateKey<K, V> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("DuplicateKey")
+            .field("map", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<K, V> DistributedMap<K, V> {
+    #[doc(hidden)]
+    pub const unsafe fn private_new(
+        name: &'static str,
+        section_start: *const (K, V),
+        section_stop: *const (K, V),
+        index: &'static OnceIndex<K, V>,
+    ) -> Self {
+        DistributedMap {
+            name,
+            section_start: StaticPtr { ptr: section_start },
+            section_stop: StaticPtr { ptr: section_stop },
+            index,
+        }
+    }
+
+    #[doc(hidden)]
+    pub unsafe fn private_typecheck(self, _entry: (K, V)) {}
+
+    /// The raw entries gathered by the linker, in unspecified order.
+    pub fn entries(&self) -> &'static [(K, V)] {
+        let stride = mem::size_of::<(K, V)>();
+        let start = self.section_start.ptr;
+        let stop = self.section_stop.ptr;
+        let len = if stride == 0 {
+            0
+        } else {
+            (stop as usize - start as usize) / stride
+        };
+        unsafe { slice::from_raw_parts(start, len) }
+    }
+
+    /// Iterate the entries in unspecified linker order.
+    pub fn iter(&self) -> slice::Iter<'static, (K, V)> {
+        self.entries().iter()
+    }
+
+    /// The number of registered entries.
+    pub fn len(&self) -> usize {
+        self.entries().len()
+    }
+
+    /// Whether any entries were registered.
+    pub fn is_empty(&self) -> bool {
+        self.entries().is_empty()
+    }
+}
+
+impl<K, V> Copy for DistributedMap<K, V> {}
+
+impl<K, V> Clone for DistributedMap<K, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, V> IntoIterator for DistributedMap<K, V> {
+    type Item = &'static (K, V);
+    type IntoIter = slice::Iter<'static, (K, V)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K, V> Debug for DistributedMap<K, V>
+where
+    K: Debug,
+    V: Debug,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_map()
+            .entries(self.entries().iter().map(|(k, v)| (k, v)))
+            .finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod lookup {
+    extern crate alloc;
+
+    use super::{DistributedMap, DuplicateKey};
+    use alloc::boxed::Box;
+    use alloc::vec::Vec;
+    use core::borrow::Borrow;
+
+    impl<K: Ord + 'static, V: 'static> DistributedMap<K, V> {
+        /// Build the lazily sorted lookup index, panicking on duplicate keys.
+        ///
+        /// A conflict-free index is built on first access and cached for the
+        /// lifetime of the program, so later calls reuse it. A map with
+        /// duplicate keys is never cached, so it is re-detected and panics on
+        /// every call.
+        fn build(&self) -> &'static [&'static (K, V)] {
+            match self.try_build() {
+                Ok(index) => index,
+                Err(err) => panic!(
+                    "distributed_map `{}` contains duplicate keys",
+                    err.name,
+                ),
+            }
+        }
+
+        /// Build the lookup index, returning the first conflict as an error
+        /// instead of panicking.
+        ///
+        /// Once a conflict-free index has been cached, this always returns it.
+        pub fn try_build(&self) -> Result<&'static [&'static (K, V)], DuplicateKey<K, V>> {
+            if let Some(index) = self.index.get() {
+                return Ok(index);
+            }
+            let mut sorted: Vec<&'static (K, V)> = self.entries().iter().collect();
+            sorted.sort_by(|a, b| a.0.cmp(&b.0));
+            for window in sorted.windows(2) {
+                if window[0].0 == window[1].0 {
+                    return Err(DuplicateKey {
+                        name: self.name,
+                        entries: (window[0], window[1]),
+                    });
+                }
+            }
+            let leaked: &'static [&'static (K, V)] = Box::leak(sorted.into_boxed_slice());
+            Ok(self.index.set(leaked))
+        }
+
+        /// Look up the value registered under `key`.
+        ///
+        /// Panics whenever called if the map contains duplicate keys, since a
+        /// conflicting map is never cached; use
+        /// [`try_build`](Self::try_build) to detect conflicts first.
+        pub fn get<Q>(&self, key: &Q) -> Option<&'static V>
+        where
+            K: Borrow<Q>,
+            Q: Ord + ?Sized,
+        {
+            let index = self.build();
+            index
+                .binary_search_by(|entry| entry.0.borrow().cmp(key))
+                .ok()
+                .map(|i| &index[i].1)
+        }
+
+        /// Whether an entry is registered under `key`.
+        pub fn contains_key<Q>(&self, key: &Q) -> bool
+        where
+            K: Borrow<Q>,
+            Q: Ord + ?Sized,
+        {
+            self.get(key).is_some()
+        }
+    }
+}
@@ -0,0 +1,296 @@
+use core::fmt::{self, Debug};
+use core::mem;
+use core::ops::Deref;
+use core::slice;
+
+/// Collection of static elements that are gathered into a contiguous section of
+/// the binary by the linker.
+///
+/// The implementation is based on `link_section` attributes that place every
+/// registered element into one named section, together with two marker symbols
+/// that bracket the section so that its bounds can be recovered at runtime. See
+/// the [crate documentation](crate) for the user-facing API.
+///
+/// # Ordering
+///
+/// The order in which elements appear inside the gathered section is whatever
+/// the linker happens to emit; it is unspecified and may differ between target
+/// platforms and even between link invocations. A distributed slice declared as
+///
+/// ```
+/// # struct Bencher;
+/// use linkme::distributed_slice;
+///
+/// #[distributed_slice(ORDER_BY = name)]
+/// pub static BENCHMARKS: [(&'static str, fn(&mut Bencher))] = [..];
+///
+/// # #[allow(dead_code)]
+/// fn name(element: &(&'static str, fn(&mut Bencher))) -> &'static str {
+///     element.0
+/// }
+/// ```
+///
+/// opts in to a deterministic ordering: the first time the slice is accessed
+/// through [`DistributedSlice::sorted_by`] the gathered elements are copied
+/// into a lazily initialized static buffer, stable-sorted by the user-supplied
+/// key function, and every subsequent access reuses that cached ordering. The
+/// unordered [`Deref`] view remains available for callers that do not care
+/// about order.
+///
+/// Ordered access requires the `alloc` feature, since the sorted snapshot is
+/// materialized on the heap and leaked for the remainder of the program.
+///
+/// # Groups
+///
+/// Elements may instead be tagged with a group label by registering them with
+/// `#[distributed_slice(SLICE, group = "setup")]`. Each registration emits a
+/// `(group, element)` record into the section;
+/// [`DistributedSlice::group`] partitions the gathered records — on first
+/// access, cached thereafter — into one contiguous `&'static [T]` view per
+/// group, which supports ordered multi-phase registries (for example `setup`
+/// versus `teardown` handlers). Because a grouped slice carries a
+/// `(group, value)` element type, its bare [`Deref`], indexing and slicing
+/// operate over those records; [`DistributedSlice::flat`] recovers the flat
+/// values-only `&'static [T]` view. Grouped access also requires the `alloc`
+/// feature.
+pub struct DistributedSlice<T: ?Sized> {
+    section_start: StaticPtr<T>,
+    section_stop: StaticPtr<T>,
+}
+
+struct StaticPtr<T: ?Sized> {
+    ptr: *const T,
+}
+
+unsafe impl<T: ?Sized> Send for StaticPtr<T> {}
+
+unsafe impl<T: ?Sized> Sync for StaticPtr<T> {}
+
+impl<T: ?Sized> Copy for StaticPtr<T> {}
+
+impl<T: ?Sized> Clone for StaticPtr<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> DistributedSlice<[T]> {
+    #[doc(hidden)]
+    pub const unsafe fn private_new(
+        section_start: *const T,
+        section_stop: *const T,
+    ) -> Self {
+        DistributedSlice {
+            section_start: StaticPtr { ptr: section_start },
+            section_stop: StaticPtr { ptr: section_stop },
+        }
+    }
+
+    #[doc(hidden)]
+    pub unsafe fn private_typecheck(self, _element: T) {}
+}
+
+impl<T> DistributedSlice<[T]> {
+    /// Retrieve the contiguous slice of elements gathered by the linker.
+    ///
+    /// This is the same slice exposed through the [`Deref`] implementation and
+    /// preserves the linker's native element order.
+    pub fn static_slice(self) -> &'static [T] {
+        let stride = mem::size_of::<T>();
+        let start = self.section_start.ptr;
+        let stop = self.section_stop.ptr;
+        let len = if stride == 0 {
+            0
+        } else {
+            (stop as usize - start as usize) / stride
+        };
+        unsafe { slice::from_raw_parts(start, len) }
+    }
+
+    /// Return an iterator over `chunk_size` elements of the slice at a time,
+    /// starting at the beginning.
+    ///
+    /// Delegates to [`[T]::chunks`](slice::chunks) over the gathered elements.
+    pub fn chunks(self, chunk_size: usize) -> slice::Chunks<'static, T> {
+        self.static_slice().chunks(chunk_size)
+    }
+
+    /// Return an iterator over all contiguous windows of length `size`.
+    ///
+    /// Delegates to [`[T]::windows`](slice::windows) over the gathered
+    /// elements.
+    pub fn windows(self, size: usize) -> slice::Windows<'static, T> {
+        self.static_slice().windows(size)
+    }
+}
+
+impl<T> Copy for DistributedSlice<[T]> {}
+
+impl<T> Clone for DistributedSlice<[T]> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Deref for DistributedSlice<[T]> {
+    type Target = [T];
+
+    fn deref(&self) -> &'static [T] {
+        self.static_slice()
+    }
+}
+
+impl<T> IntoIterator for DistributedSlice<[T]> {
+    type Item = &'static T;
+    type IntoIter = slice::Iter<'static, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.static_slice().iter()
+    }
+}
+
+impl<T> Debug for DistributedSlice<[T]>
+where
+    T: Debug,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.debug_list().entries(self.static_slice()).finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod ordered {
+    extern crate alloc;
+
+    use super::DistributedSlice;
+    use crate::once::Once;
+    use alloc::boxed::Box;
+    use alloc::vec::Vec;
+
+    /// Lazily initialized cache holding a sorted snapshot of a distributed
+    /// slice, emitted by the `#[distributed_slice(ORDER_BY = ...)]` attribute
+    /// alongside the slice it orders. A grouped slice also uses one to cache
+    /// the unsorted values-only copy returned by `flat`.
+    pub type OnceSlice<T> = Once<T>;
+
+    impl<T: Clone + 'static> DistributedSlice<[T]> {
+        /// Return a sorted view of the gathered elements.
+        ///
+        /// On the first call the raw elements are cloned into `cache`,
+        /// stable-sorted by `key`, and the leaked snapshot is returned; later
+        /// calls reuse the cached ordering and ignore `key`. The `cache` static
+        /// and `key` function are both supplied by the
+        /// `#[distributed_slice(ORDER_BY = ...)]` attribute, which wraps this
+        /// into a zero-argument `SLICE.sorted()` accessor — callers of an
+        /// ordered slice never thread them through by hand.
+        ///
+        /// Elements are required to be [`Clone`] because the sorted view is a
+        /// contiguous copy of the gathered section rather than a reordering in
+        /// place, and ordered access requires the `alloc` feature since that
+        /// copy is materialized on the heap and leaked for the remainder of the
+        /// program.
+        pub fn sorted_by<K: Ord>(
+            self,
+            cache: &'static OnceSlice<T>,
+            key: fn(&T) -> K,
+        ) -> &'static [T] {
+            if let Some(sorted) = cache.get() {
+                return sorted;
+            }
+            let mut snapshot: Vec<T> = self.static_slice().to_vec();
+            snapshot.sort_by_cached_key(|element| key(element));
+            let sorted: &'static [T] = Box::leak(snapshot.into_boxed_slice());
+            cache.set(sorted)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub use ordered::OnceSlice;
+
+#[cfg(feature = "alloc")]
+mod grouped {
+    extern crate alloc;
+
+    use super::{DistributedSlice, OnceSlice};
+    use crate::once::Once;
+    use alloc::boxed::Box;
+    use alloc::vec::Vec;
+
+    /// Lazily built partition of a distributed slice into per-group contiguous
+    /// ranges, emitted by the `#[distributed_slice(SLICE, group = "...")]`
+    /// attribute alongside the slice it labels.
+    ///
+    /// The partition is a leaked table mapping each group label to a contiguous
+    /// `&'static [T]` view, cached in the shared write-once [`Once`] primitive.
+    pub type OnceGroups<T> = Once<(&'static str, &'static [T])>;
+
+    impl<T: Clone + 'static> DistributedSlice<[(&'static str, T)]> {
+        /// Return the contiguous sub-slice of elements tagged with the given
+        /// group label.
+        ///
+        /// The first call partitions the gathered `(group, element)` records
+        /// into one contiguous buffer per group — groups appear in the order
+        /// their first element was gathered, and elements keep their relative
+        /// order within a group — and caches the partition in `cache`. An
+        /// unknown label yields an empty slice. The `cache` static is supplied
+        /// by the `#[distributed_slice(SLICE, group = "...")]` attribute.
+        pub fn group(self, cache: &'static OnceGroups<T>, name: &str) -> &'static [T] {
+            let table = match cache.get() {
+                Some(table) => table,
+                None => self.build_groups(cache),
+            };
+            table
+                .iter()
+                .find(|(label, _)| *label == name)
+                .map_or(&[][..], |(_, elements)| *elements)
+        }
+
+        /// Return the group labels stripped off, i.e. a flat `&'static [T]` view
+        /// of just the registered values in gathered order.
+        ///
+        /// A grouped slice carries a `(group, value)` element type so that
+        /// [`group`](Self::group) can partition it, which means the bare
+        /// [`Deref`](core::ops::Deref), indexing and slicing operate over the
+        /// `(&'static str, T)` records. This accessor recovers the flat
+        /// values-only view the ungrouped slice would have exposed, cloning the
+        /// values into a leaked buffer cached in `cache` on first access.
+        pub fn flat(self, cache: &'static OnceSlice<T>) -> &'static [T] {
+            if let Some(values) = cache.get() {
+                return values;
+            }
+            let snapshot: Vec<T> = self
+                .static_slice()
+                .iter()
+                .map(|(_, value)| value.clone())
+                .collect();
+            cache.set(Box::leak(snapshot.into_boxed_slice()))
+        }
+
+        fn build_groups(
+            self,
+            cache: &'static OnceGroups<T>,
+        ) -> &'static [(&'static str, &'static [T])] {
+            let mut order: Vec<&'static str> = Vec::new();
+            let mut buckets: Vec<Vec<T>> = Vec::new();
+            for (label, element) in self.static_slice() {
+                match order.iter().position(|seen| seen == label) {
+                    Some(i) => buckets[i].push(element.clone()),
+                    None => {
+                        order.push(label);
+                        buckets.push(alloc::vec![element.clone()]);
+                    }
+                }
+            }
+            let mut table: Vec<(&'static str, &'static [T])> = Vec::with_capacity(order.len());
+            for (label, bucket) in order.into_iter().zip(buckets) {
+                let elements: &'static [T] = Box::leak(bucket.into_boxed_slice());
+                table.push((label, elements));
+            }
+            cache.set(Box::leak(table.into_boxed_slice()))
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub use grouped::OnceGroups;
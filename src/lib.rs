@@ -110,11 +110,14 @@
     clippy::unused_self
 )]
 
+mod distributed_map;
 mod distributed_slice;
+mod once;
 
 #[doc(hidden)]
 pub mod private;
 
 pub use linkme_impl::*;
 
+pub use crate::distributed_map::{DistributedMap, DuplicateKey};
 pub use crate::distributed_slice::DistributedSlice;
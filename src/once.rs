@@ -0,0 +1,55 @@
+use core::slice;
+use core::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
+
+/// Lazily initialized, write-once cache of a leaked `&'static [E]`.
+///
+/// The lazy views layered on top of a distributed slice or map — the sorted
+/// snapshot, the per-group partition, and the keyed lookup index — are all the
+/// same shape: a slice that is computed on first access, leaked, and then read
+/// back on every subsequent access. This is the single primitive they share.
+///
+/// One of these is emitted by the attribute macro alongside the item it serves;
+/// the pointer is published exactly once behind an atomic compare-exchange, so
+/// concurrent first accesses all observe the same winning snapshot.
+pub struct Once<E: 'static> {
+    ptr: AtomicPtr<E>,
+    len: AtomicUsize,
+}
+
+impl<E: 'static> Once<E> {
+    pub const fn new() -> Self {
+        Once {
+            ptr: AtomicPtr::new(core::ptr::null_mut()),
+            len: AtomicUsize::new(0),
+        }
+    }
+
+    pub(crate) fn get(&self) -> Option<&'static [E]> {
+        let ptr = self.ptr.load(Ordering::Acquire);
+        if ptr.is_null() {
+            return None;
+        }
+        let len = self.len.load(Ordering::Acquire);
+        Some(unsafe { slice::from_raw_parts(ptr, len) })
+    }
+
+    /// Publish `slice` as the cached value, returning whichever snapshot ends up
+    /// winning a race. The length is stored before the pointer so that a reader
+    /// observing a non-null pointer always sees the matching length; every racer
+    /// derives its snapshot from the same gathered elements, so a loser storing
+    /// its own length first is harmless — the value is identical.
+    pub(crate) fn set(&self, slice: &'static [E]) -> &'static [E] {
+        self.len.store(slice.len(), Ordering::Release);
+        match self.ptr.compare_exchange(
+            core::ptr::null_mut(),
+            slice.as_ptr() as *mut E,
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        ) {
+            Ok(_) => slice,
+            // Another thread published first; discard our snapshot and use
+            // theirs so every caller observes the same result.
+            Err(ptr) => unsafe { slice::from_raw_parts(ptr, self.len.load(Ordering::Acquire)) },
+        }
+    }
+}
@@ -0,0 +1,5 @@
+pub use crate::distributed_map::{DistributedMap, OnceIndex};
+pub use crate::distributed_slice::DistributedSlice;
+
+#[cfg(feature = "alloc")]
+pub use crate::distributed_slice::{OnceGroups, OnceSlice};
@@ -0,0 +1,133 @@
+#![cfg(feature = "alloc")]
+
+use linkme::{distributed_map, distributed_slice};
+
+// Ordered slice: ORDER_BY opts in to a deterministic, key-sorted view while the
+// flat Deref keeps the linker's native order.
+
+#[distributed_slice(ORDER_BY = weight)]
+static STEPS: [(&'static str, u32)] = [..];
+
+fn weight(element: &(&'static str, u32)) -> u32 {
+    element.1
+}
+
+#[distributed_slice(STEPS)]
+static STEP_C: (&'static str, u32) = ("c", 3);
+
+#[distributed_slice(STEPS)]
+static STEP_A: (&'static str, u32) = ("a", 1);
+
+#[distributed_slice(STEPS)]
+static STEP_B: (&'static str, u32) = ("b", 2);
+
+#[test]
+fn test_sorted_is_reproducible() {
+    let sorted: Vec<u32> = STEPS.sorted().iter().map(|(_, w)| *w).collect();
+    assert_eq!(sorted, [1, 2, 3]);
+    // A second access reuses the cached ordering.
+    assert_eq!(STEPS.sorted().as_ptr(), STEPS.sorted().as_ptr());
+}
+
+// Grouped slice: elements tagged with `group = "..."` partition into contiguous
+// per-group sub-slices, while `flat` recovers the values-only view.
+
+#[distributed_slice]
+static PHASES: [(&'static str, fn() -> &'static str)] = [..];
+
+#[distributed_slice(PHASES, group = "setup")]
+static OPEN_DB: fn() -> &'static str = || "open_db";
+
+#[distributed_slice(PHASES, group = "teardown")]
+static CLOSE_DB: fn() -> &'static str = || "close_db";
+
+#[distributed_slice(PHASES, group = "setup")]
+static WARM_CACHE: fn() -> &'static str = || "warm_cache";
+
+#[test]
+fn test_group_partitions_in_order() {
+    let setup: Vec<&'static str> = PHASES.group("setup").iter().map(|f| f()).collect();
+    // Within a group, elements keep the linker's (unspecified) gathered order.
+    let gathered: Vec<&'static str> = PHASES
+        .iter()
+        .filter(|(label, _)| *label == "setup")
+        .map(|(_, f)| f())
+        .collect();
+    assert_eq!(setup, gathered);
+    let mut names = setup.clone();
+    names.sort_unstable();
+    assert_eq!(names, ["open_db", "warm_cache"]);
+
+    let teardown: Vec<&'static str> = PHASES.group("teardown").iter().map(|f| f()).collect();
+    assert_eq!(teardown, ["close_db"]);
+}
+
+#[test]
+fn test_unknown_group_is_empty() {
+    assert!(PHASES.group("migrate").is_empty());
+}
+
+#[test]
+fn test_flat_strips_group_label() {
+    assert_eq!(PHASES.flat().len(), PHASES.len());
+}
+
+// Keyed map: get, Borrow-keyed lookup, empty lookup, and the duplicate-key
+// policy.
+
+#[distributed_map]
+static HANDLERS: [(&'static str, fn() -> i32)] = [..];
+
+#[distributed_map(HANDLERS)]
+static PING: (&'static str, fn() -> i32) = ("ping", || 1);
+
+#[distributed_map(HANDLERS)]
+static PONG: (&'static str, fn() -> i32) = ("pong", || 2);
+
+#[test]
+fn test_map_get() {
+    assert_eq!(HANDLERS.get("ping").map(|f| f()), Some(1));
+    assert_eq!(HANDLERS.get("pong").map(|f| f()), Some(2));
+    assert_eq!(HANDLERS.get("nope"), None);
+    assert!(HANDLERS.contains_key("ping"));
+}
+
+#[test]
+fn test_map_borrowed_key() {
+    // `get` accepts any `Q` the key borrows as, so a short-lived `&str` borrowed
+    // from an owned `String` works against `&'static str` keys.
+    let query = String::from("ping");
+    assert_eq!(HANDLERS.get(query.as_str()).map(|f| f()), Some(1));
+}
+
+#[test]
+fn test_map_try_build_ok() {
+    assert!(HANDLERS.try_build().is_ok());
+}
+
+#[distributed_map]
+static EMPTY: [(&'static str, u8)] = [..];
+
+#[test]
+fn test_empty_map_lookup() {
+    assert!(EMPTY.is_empty());
+    assert_eq!(EMPTY.get("anything"), None);
+    assert!(EMPTY.try_build().unwrap().is_empty());
+}
+
+#[distributed_map]
+static COLLIDING: [(&'static str, u8)] = [..];
+
+#[distributed_map(COLLIDING)]
+static DUP_ONE: (&'static str, u8) = ("dup", 1);
+
+#[distributed_map(COLLIDING)]
+static DUP_TWO: (&'static str, u8) = ("dup", 2);
+
+#[test]
+fn test_duplicate_key_is_reported() {
+    let err = COLLIDING.try_build().unwrap_err();
+    let (a, b) = err.entries();
+    assert_eq!(a.0, "dup");
+    assert_eq!(b.0, "dup");
+}